@@ -93,6 +93,33 @@ impl<'de> Deserialize<'de> for Txid {
     }
 }
 
+impl fmt::Display for Txid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_rpc_hex())
+    }
+}
+
+impl Txid {
+    /// Encodes as hex in Bitcoin Core/RPC display order, i.e. with the
+    /// internal (little-endian) byte order reversed.
+    pub fn to_rpc_hex(&self) -> String {
+        let mut reversed = self.0;
+        reversed.reverse();
+        hex::encode(reversed)
+    }
+
+    /// Parses RPC-order hex back into internal byte order.
+    pub fn from_rpc_hex(s: &str) -> Result<Self, BitcoinError> {
+        let decoded = hex::decode(s).map_err(|_| BitcoinError::InvalidFormat)?;
+        if decoded.len() != 32 {
+            return Err(BitcoinError::InvalidFormat);
+        }
+        let mut arr: [u8; 32] = decoded.try_into().unwrap();
+        arr.reverse();
+        Ok(Txid(arr))
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct OutPoint {
     pub txid: Txid,
@@ -155,6 +182,258 @@ impl Deref for Script {
     }
 }
 
+const OP_DUP: u8 = 0x76;
+const OP_EQUAL: u8 = 0x87;
+const OP_EQUALVERIFY: u8 = 0x88;
+const OP_HASH160: u8 = 0xa9;
+const OP_CHECKSIG: u8 = 0xac;
+const OP_RETURN: u8 = 0x6a;
+
+/// The standard scriptPubKey shapes `Script::classify` recognizes.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum ScriptType {
+    P2PKH,
+    P2SH,
+    P2WPKH,
+    P2WSH,
+    P2TR,
+    OpReturn,
+    NonStandard,
+}
+
+impl Script {
+    /// Extracts `(witness_version, program)` from a segwit/taproot
+    /// scriptPubKey (`OP_n <push> <program>`), or `None` if `self` isn't one.
+    pub fn witness_program(&self) -> Option<(u8, Vec<u8>)> {
+        let bytes = &self.bytes;
+        if bytes.len() < 2 {
+            return None;
+        }
+        let version = match bytes[0] {
+            0x00 => 0,
+            op @ 0x51..=0x60 => op - 0x50,
+            _ => return None,
+        };
+        let push_len = bytes[1] as usize;
+        if !(2..=40).contains(&push_len) || bytes.len() != 2 + push_len {
+            return None;
+        }
+        Some((version, bytes[2..].to_vec()))
+    }
+
+    /// Classifies `self` as one of the standard output types by pattern
+    /// matching its raw opcode bytes, so callers can route signature
+    /// verification (ECDSA vs. Schnorr) based on the detected shape.
+    pub fn classify(&self) -> ScriptType {
+        let bytes = &self.bytes;
+
+        if bytes.len() == 25
+            && bytes[0] == OP_DUP
+            && bytes[1] == OP_HASH160
+            && bytes[2] == 0x14
+            && bytes[23] == OP_EQUALVERIFY
+            && bytes[24] == OP_CHECKSIG
+        {
+            return ScriptType::P2PKH;
+        }
+
+        if bytes.len() == 23 && bytes[0] == OP_HASH160 && bytes[1] == 0x14 && bytes[22] == OP_EQUAL {
+            return ScriptType::P2SH;
+        }
+
+        if let Some((version, program)) = self.witness_program() {
+            return match (version, program.len()) {
+                (0, 20) => ScriptType::P2WPKH,
+                (0, 32) => ScriptType::P2WSH,
+                (1, 32) => ScriptType::P2TR,
+                _ => ScriptType::NonStandard,
+            };
+        }
+
+        if bytes.first() == Some(&OP_RETURN) {
+            return ScriptType::OpReturn;
+        }
+
+        ScriptType::NonStandard
+    }
+
+    /// Encodes `self` as a bech32/bech32m address if it is a standard
+    /// segwit/taproot scriptPubKey (P2WPKH, P2WSH, or P2TR).
+    pub fn to_address(&self, hrp: &str) -> Option<String> {
+        match self.classify() {
+            ScriptType::P2WPKH | ScriptType::P2WSH | ScriptType::P2TR => {}
+            _ => return None,
+        }
+        let (version, program) = self.witness_program()?;
+        Some(WitnessProgram::new(version, program).encode(hrp))
+    }
+
+    /// Decodes a bech32/bech32m address back into the scriptPubKey it
+    /// represents.
+    pub fn from_address(address: &str) -> Result<Self, BitcoinError> {
+        let (_, witness_program) = WitnessProgram::decode(address)?;
+        let opcode = if witness_program.version == 0 {
+            0x00
+        } else {
+            0x50 + witness_program.version
+        };
+        let mut bytes = vec![opcode, witness_program.program.len() as u8];
+        bytes.extend(&witness_program.program);
+        Ok(Script::new(bytes))
+    }
+}
+
+const BECH32_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc830a3;
+
+fn bech32_polymod(values: &[u8]) -> u32 {
+    const GEN: [u32; 5] = [
+        0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3,
+    ];
+    let mut chk: u32 = 1;
+    for &v in values {
+        let top = chk >> 25;
+        chk = (chk & 0x01ff_ffff) << 5 ^ (v as u32);
+        for (i, g) in GEN.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+fn bech32_hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut res: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+    res.push(0);
+    res.extend(hrp.bytes().map(|b| b & 31));
+    res
+}
+
+fn bech32_create_checksum(hrp: &str, data: &[u8], const_value: u32) -> Vec<u8> {
+    let mut values = bech32_hrp_expand(hrp);
+    values.extend(data);
+    values.extend(&[0u8; 6]);
+    let polymod = bech32_polymod(&values) ^ const_value;
+    (0..6).map(|i| ((polymod >> (5 * (5 - i))) & 31) as u8).collect()
+}
+
+fn bech32_verify_checksum(hrp: &str, data: &[u8], const_value: u32) -> bool {
+    let mut values = bech32_hrp_expand(hrp);
+    values.extend(data);
+    bech32_polymod(&values) == const_value
+}
+
+/// Repacks a byte stream between `from`-bit and `to`-bit groups, as used to
+/// squash the 8-bit witness program into the 5-bit groups bech32 encodes.
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let mut ret = Vec::new();
+    let maxv = (1u32 << to) - 1;
+    for &value in data {
+        let value = value as u32;
+        if (value >> from) != 0 {
+            return None;
+        }
+        acc = (acc << from) | value;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            ret.push(((acc >> bits) & maxv) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            ret.push(((acc << (to - bits)) & maxv) as u8);
+        }
+    } else if bits >= from || ((acc << (to - bits)) & maxv) != 0 {
+        return None;
+    }
+    Some(ret)
+}
+
+/// A segwit/taproot witness program: a version (0-16) and a 2-to-40-byte
+/// program, the payload a bech32/bech32m address encodes.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct WitnessProgram {
+    pub version: u8,
+    pub program: Vec<u8>,
+}
+
+impl WitnessProgram {
+    pub fn new(version: u8, program: Vec<u8>) -> Self {
+        Self { version, program }
+    }
+
+    /// Encodes as a bech32 (version 0) or bech32m (version 1-16) address
+    /// under the given human-readable part (e.g. `"bc"`, `"tb"`).
+    pub fn encode(&self, hrp: &str) -> String {
+        let mut data = vec![self.version];
+        data.extend(convert_bits(&self.program, 8, 5, true).unwrap());
+
+        let const_value = if self.version == 0 { BECH32_CONST } else { BECH32M_CONST };
+        let checksum = bech32_create_checksum(hrp, &data, const_value);
+
+        let mut res = String::with_capacity(hrp.len() + 1 + data.len() + checksum.len());
+        res.push_str(hrp);
+        res.push('1');
+        for group in data.iter().chain(checksum.iter()) {
+            res.push(BECH32_CHARSET[*group as usize] as char);
+        }
+        res
+    }
+
+    /// Decodes a bech32/bech32m address, returning its HRP alongside the
+    /// witness program, and rejecting a checksum computed under the wrong
+    /// variant (bech32 for v0, bech32m for v1+).
+    pub fn decode(s: &str) -> Result<(String, WitnessProgram), BitcoinError> {
+        let lower = s.to_lowercase();
+        let sep = lower.rfind('1').ok_or(BitcoinError::InvalidFormat)?;
+        if sep == 0 || sep + 7 > lower.len() {
+            return Err(BitcoinError::InvalidFormat);
+        }
+        let hrp = &lower[..sep];
+        let data_part = &lower[sep + 1..];
+
+        let mut data = Vec::with_capacity(data_part.len());
+        for c in data_part.chars() {
+            let pos = BECH32_CHARSET
+                .iter()
+                .position(|&x| x as char == c)
+                .ok_or(BitcoinError::InvalidFormat)?;
+            data.push(pos as u8);
+        }
+
+        // Need at least one 5-bit group (the witness version) ahead of the
+        // 6-symbol checksum, or there's no payload to decode.
+        if data.len() <= 6 {
+            return Err(BitcoinError::InvalidFormat);
+        }
+
+        let version = data[0];
+        let const_value = if version == 0 { BECH32_CONST } else { BECH32M_CONST };
+        if !bech32_verify_checksum(hrp, &data, const_value) {
+            return Err(BitcoinError::InvalidFormat);
+        }
+        if version > 16 {
+            return Err(BitcoinError::InvalidFormat);
+        }
+
+        let payload = &data[..data.len() - 6];
+        let program = convert_bits(&payload[1..], 5, 8, false).ok_or(BitcoinError::InvalidFormat)?;
+        if !(2..=40).contains(&program.len()) {
+            return Err(BitcoinError::InvalidFormat);
+        }
+        if version == 0 && program.len() != 20 && program.len() != 32 {
+            return Err(BitcoinError::InvalidFormat);
+        }
+
+        Ok((hrp.to_string(), WitnessProgram::new(version, program)))
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct TransactionInput {
     pub previous_output: OutPoint,
@@ -188,24 +467,149 @@ impl TransactionInput {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct TransactionOutput {
+    pub value: u64,
+    pub script_pubkey: Script,
+}
+
+impl TransactionOutput {
+    pub fn new(value: u64, script_pubkey: Script) -> Self {
+        Self { value, script_pubkey }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = self.value.to_le_bytes().to_vec();
+        res.extend(self.script_pubkey.to_bytes());
+        res
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), BitcoinError> {
+        if bytes.len() < 8 {
+            return Err(BitcoinError::InsufficientBytes);
+        }
+        let value = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+        let (script_pubkey, used) = Script::from_bytes(&bytes[8..])?;
+        Ok((TransactionOutput::new(value, script_pubkey), 8 + used))
+    }
+}
+
+/// A per-input witness stack, as introduced by BIP 144.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Witness {
+    pub stack: Vec<Vec<u8>>,
+}
+
+impl Witness {
+    pub fn new(stack: Vec<Vec<u8>>) -> Self {
+        Self { stack }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = CompactSize::new(self.stack.len() as u64).to_bytes();
+        for item in &self.stack {
+            res.extend(CompactSize::new(item.len() as u64).to_bytes());
+            res.extend(item);
+        }
+        res
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), BitcoinError> {
+        let (cs, used) = CompactSize::from_bytes(bytes)?;
+        let mut offset = used;
+        let mut stack = Vec::new();
+        for _ in 0..cs.value {
+            let (item_cs, item_used) = CompactSize::from_bytes(&bytes[offset..])?;
+            offset += item_used;
+            let len = item_cs.value as usize;
+            if bytes.len() < offset + len {
+                return Err(BitcoinError::InsufficientBytes);
+            }
+            stack.push(bytes[offset..offset + len].to_vec());
+            offset += len;
+        }
+        Ok((Witness::new(stack), offset))
+    }
+}
+
+const SEGWIT_MARKER: u8 = 0x00;
+const SEGWIT_FLAG: u8 = 0x01;
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct BitcoinTransaction {
     pub version: u32,
     pub inputs: Vec<TransactionInput>,
+    pub outputs: Vec<TransactionOutput>,
+    pub witnesses: Vec<Witness>,
     pub lock_time: u32,
 }
 
 impl BitcoinTransaction {
-    pub fn new(version: u32, inputs: Vec<TransactionInput>, lock_time: u32) -> Self {
-        Self { version, inputs, lock_time }
+    pub fn new(
+        version: u32,
+        inputs: Vec<TransactionInput>,
+        outputs: Vec<TransactionOutput>,
+        witnesses: Vec<Witness>,
+        lock_time: u32,
+    ) -> Self {
+        Self { version, inputs, outputs, witnesses, lock_time }
+    }
+
+    fn is_segwit(&self) -> bool {
+        !self.witnesses.is_empty()
+    }
+
+    /// The txid: double-SHA256 of the legacy (non-witness) serialization,
+    /// so it's stable across malleation of witness data.
+    pub fn txid(&self) -> Txid {
+        Txid(double_sha256(&self.to_bytes_legacy()))
+    }
+
+    /// The wtxid: double-SHA256 of the full serialization, including the
+    /// BIP 144 marker/flag and witness stacks when present.
+    pub fn wtxid(&self) -> Txid {
+        Txid(double_sha256(&self.to_bytes()))
     }
 
+    /// Serializes the transaction including the BIP 144 marker/flag and per-input
+    /// witness stacks whenever any witness data is present; falls back to the
+    /// legacy layout otherwise.
     pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = self.version.to_le_bytes().to_vec();
+        if self.is_segwit() {
+            res.push(SEGWIT_MARKER);
+            res.push(SEGWIT_FLAG);
+        }
+        res.extend(CompactSize::new(self.inputs.len() as u64).to_bytes());
+        for inp in &self.inputs {
+            res.extend(inp.to_bytes());
+        }
+        res.extend(CompactSize::new(self.outputs.len() as u64).to_bytes());
+        for out in &self.outputs {
+            res.extend(out.to_bytes());
+        }
+        if self.is_segwit() {
+            for witness in &self.witnesses {
+                res.extend(witness.to_bytes());
+            }
+        }
+        res.extend(&self.lock_time.to_le_bytes());
+        res
+    }
+
+    /// Serializes the transaction without the marker/flag/witness fields, i.e.
+    /// the payload whose double-SHA256 is the legacy txid even for segwit
+    /// transactions.
+    pub fn to_bytes_legacy(&self) -> Vec<u8> {
         let mut res = self.version.to_le_bytes().to_vec();
         res.extend(CompactSize::new(self.inputs.len() as u64).to_bytes());
         for inp in &self.inputs {
             res.extend(inp.to_bytes());
         }
+        res.extend(CompactSize::new(self.outputs.len() as u64).to_bytes());
+        for out in &self.outputs {
+            res.extend(out.to_bytes());
+        }
         res.extend(&self.lock_time.to_le_bytes());
         res
     }
@@ -215,20 +619,41 @@ impl BitcoinTransaction {
             return Err(BitcoinError::InsufficientBytes);
         }
         let version = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
-        let (cs, used1) = CompactSize::from_bytes(&bytes[4..])?;
-        let mut offset = 4 + used1;
+        let segwit = bytes.len() >= 6 && bytes[4] == SEGWIT_MARKER && bytes[5] == SEGWIT_FLAG;
+        let mut offset = if segwit { 6 } else { 4 };
+
+        let (cs, used1) = CompactSize::from_bytes(&bytes[offset..])?;
+        offset += used1;
         let mut inputs = Vec::new();
         for _ in 0..cs.value {
             let (input, used) = TransactionInput::from_bytes(&bytes[offset..])?;
             inputs.push(input);
             offset += used;
         }
+        let (out_cs, used2) = CompactSize::from_bytes(&bytes[offset..])?;
+        offset += used2;
+        let mut outputs = Vec::new();
+        for _ in 0..out_cs.value {
+            let (output, used) = TransactionOutput::from_bytes(&bytes[offset..])?;
+            outputs.push(output);
+            offset += used;
+        }
+
+        let mut witnesses = Vec::new();
+        if segwit {
+            for _ in 0..inputs.len() {
+                let (witness, used) = Witness::from_bytes(&bytes[offset..])?;
+                witnesses.push(witness);
+                offset += used;
+            }
+        }
+
         if bytes.len() < offset + 4 {
             return Err(BitcoinError::InsufficientBytes);
         }
         let lock_time = u32::from_le_bytes(bytes[offset..offset+4].try_into().unwrap());
         Ok((
-            BitcoinTransaction::new(version, inputs, lock_time),
+            BitcoinTransaction::new(version, inputs, outputs, witnesses, lock_time),
             offset+4
         ))
     }
@@ -242,6 +667,240 @@ impl fmt::Display for BitcoinTransaction {
             writeln!(f, "ScriptSig Length: {}", inp.script_sig.bytes.len())?;
             writeln!(f, "ScriptSig Bytes: {:?}", inp.script_sig.bytes)?;
         }
+        for out in &self.outputs {
+            writeln!(f, "Output Value: {}", out.value)?;
+            writeln!(f, "ScriptPubKey Length: {}", out.script_pubkey.bytes.len())?;
+        }
         writeln!(f, "Lock Time: {}", self.lock_time)
     }
 }
+
+/// A Bitcoin block header: the fixed 80-byte structure that commits to a
+/// block's transactions via `merkle_root` and to proof-of-work via `bits`.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct BlockHeader {
+    pub version: u32,
+    pub prev_blockhash: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub time: u32,
+    pub bits: u32,
+    pub nonce: u32,
+}
+
+impl BlockHeader {
+    pub fn new(
+        version: u32,
+        prev_blockhash: [u8; 32],
+        merkle_root: [u8; 32],
+        time: u32,
+        bits: u32,
+        nonce: u32,
+    ) -> Self {
+        Self { version, prev_blockhash, merkle_root, time, bits, nonce }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = Vec::with_capacity(80);
+        res.extend(&self.version.to_le_bytes());
+        res.extend(&self.prev_blockhash);
+        res.extend(&self.merkle_root);
+        res.extend(&self.time.to_le_bytes());
+        res.extend(&self.bits.to_le_bytes());
+        res.extend(&self.nonce.to_le_bytes());
+        res
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), BitcoinError> {
+        if bytes.len() < 80 {
+            return Err(BitcoinError::InsufficientBytes);
+        }
+        let version = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
+        let prev_blockhash: [u8; 32] = bytes[4..36].try_into().unwrap();
+        let merkle_root: [u8; 32] = bytes[36..68].try_into().unwrap();
+        let time = u32::from_le_bytes(bytes[68..72].try_into().unwrap());
+        let bits = u32::from_le_bytes(bytes[72..76].try_into().unwrap());
+        let nonce = u32::from_le_bytes(bytes[76..80].try_into().unwrap());
+        Ok((
+            BlockHeader::new(version, prev_blockhash, merkle_root, time, bits, nonce),
+            80,
+        ))
+    }
+
+    /// Decompresses the compact `bits` field into a full 256-bit target,
+    /// written big-endian. Mirrors Bitcoin Core's `arith_uint256::SetCompact`:
+    /// the top byte of `bits` is the exponent, the low three bytes the
+    /// mantissa, and a set sign bit on the mantissa clamps the result to zero.
+    pub fn target(&self) -> [u8; 32] {
+        let exponent = (self.bits >> 24) as i32;
+        let mantissa = self.bits & 0x007F_FFFF;
+
+        if self.bits & 0x0080_0000 != 0 {
+            return [0u8; 32];
+        }
+
+        let mantissa_bytes = mantissa.to_be_bytes();
+        let mantissa_bytes = &mantissa_bytes[1..]; // low 3 bytes, big-endian
+
+        // Unshifted (exponent == 3), the mantissa sits at target[29..32]; each
+        // unit of exponent above/below 3 slides it one byte towards/away from
+        // the most-significant end. Bytes that land outside the 32-byte
+        // target are simply dropped, clamping on both overflow and underflow.
+        let mut target = [0u8; 32];
+        let start = 29 - (exponent - 3);
+        for (i, byte) in mantissa_bytes.iter().enumerate() {
+            let pos = start + i as i32;
+            if (0..32).contains(&pos) {
+                target[pos as usize] = *byte;
+            }
+        }
+        target
+    }
+}
+
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// Minimal, self-contained SHA-256 (FIPS 180-4) used internally for txid and
+/// merkle-root hashing so the crate doesn't need an external hashing dependency.
+fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut h: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a,
+        0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+    ];
+
+    let mut msg = data.to_vec();
+    let bit_len = (data.len() as u64) * 8;
+    msg.push(0x80);
+    while msg.len() % 64 != 56 {
+        msg.push(0);
+    }
+    msg.extend(&bit_len.to_be_bytes());
+
+    for chunk in msg.chunks(64) {
+        let mut w = [0u32; 64];
+        for i in 0..16 {
+            w[i] = u32::from_be_bytes(chunk[i * 4..i * 4 + 4].try_into().unwrap());
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let (mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh) =
+            (h[0], h[1], h[2], h[3], h[4], h[5], h[6], h[7]);
+
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ ((!e) & g);
+            let temp1 = hh
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(SHA256_K[i])
+                .wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(maj);
+
+            hh = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp1.wrapping_add(temp2);
+        }
+
+        h[0] = h[0].wrapping_add(a);
+        h[1] = h[1].wrapping_add(b);
+        h[2] = h[2].wrapping_add(c);
+        h[3] = h[3].wrapping_add(d);
+        h[4] = h[4].wrapping_add(e);
+        h[5] = h[5].wrapping_add(f);
+        h[6] = h[6].wrapping_add(g);
+        h[7] = h[7].wrapping_add(hh);
+    }
+
+    let mut out = [0u8; 32];
+    for (i, word) in h.iter().enumerate() {
+        out[i * 4..i * 4 + 4].copy_from_slice(&word.to_be_bytes());
+    }
+    out
+}
+
+/// Bitcoin's hash256: SHA-256 applied twice.
+fn double_sha256(data: &[u8]) -> [u8; 32] {
+    sha256(&sha256(data))
+}
+
+/// A Merkle branch proving that `tx_hash` is included under `merkle_root`,
+/// mirroring the shape used by SPV clients: each step names a sibling hash
+/// and which side it sits on.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct MerkleProof {
+    pub tx_hash: [u8; 32],
+    pub branch: Vec<([u8; 32], bool)>,
+    pub merkle_root: [u8; 32],
+}
+
+impl MerkleProof {
+    pub fn new(tx_hash: [u8; 32], branch: Vec<([u8; 32], bool)>, merkle_root: [u8; 32]) -> Self {
+        Self { tx_hash, branch, merkle_root }
+    }
+
+    /// Folds `tx_hash` up through `branch`, hashing with each sibling in the
+    /// order its `bool` dictates (`true` = sibling is on the right), and
+    /// checks the result against `merkle_root`.
+    pub fn verify(&self) -> bool {
+        let mut current = self.tx_hash;
+        for (sibling, sibling_is_right) in &self.branch {
+            let mut data = Vec::with_capacity(64);
+            if *sibling_is_right {
+                data.extend(&current);
+                data.extend(sibling);
+            } else {
+                data.extend(sibling);
+                data.extend(&current);
+            }
+            current = double_sha256(&data);
+        }
+        current == self.merkle_root
+    }
+}
+
+/// Builds a merkle root from a list of txids, duplicating the last hash at
+/// each level when the level has an odd count (Bitcoin's well-known
+/// CVE-2012-2459-adjacent convention).
+pub fn compute_merkle_root(txids: &[[u8; 32]]) -> [u8; 32] {
+    if txids.is_empty() {
+        return [0u8; 32];
+    }
+
+    let mut level: Vec<[u8; 32]> = txids.to_vec();
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(*level.last().unwrap());
+        }
+        let mut next = Vec::with_capacity(level.len() / 2);
+        for pair in level.chunks(2) {
+            let mut data = Vec::with_capacity(64);
+            data.extend(&pair[0]);
+            data.extend(&pair[1]);
+            next.push(double_sha256(&data));
+        }
+        level = next;
+    }
+    level[0]
+}